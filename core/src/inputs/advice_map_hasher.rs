@@ -0,0 +1,89 @@
+use crate::{chiplets::hasher, Felt, FieldElement, Word};
+
+// ADVICE MAP HASHER
+// ================================================================================================
+
+/// The width, in field elements, of the RPO sponge state used to key the advice map.
+const STATE_WIDTH: usize = 12;
+
+/// The number of elements in the sponge's rate portion; values are absorbed in chunks of this
+/// size.
+const RATE_WIDTH: usize = 8;
+
+/// The number of elements in the sponge's capacity portion.
+const CAPACITY_WIDTH: usize = STATE_WIDTH - RATE_WIDTH;
+
+/// Computes the RPO sponge digest of `values`, for use as an advice map key.
+///
+/// The capacity portion of the state is initialized with a domain-separating tag derived from
+/// `values.len()` (so that two value slices which differ only in how they are chunked can never
+/// collide), `values` is absorbed into the rate portion in chunks of [RATE_WIDTH] (applying a
+/// permutation after each chunk, including a final partial chunk, which is zero-padded), and the
+/// first [CAPACITY_WIDTH] elements of the rate portion are squeezed out as the 4-element digest.
+///
+/// Hosts can use this function to key a large secret input by its digest, so that the underlying
+/// values can be registered and looked up via
+/// [ProgramInputs::with_advice_map](super::ProgramInputs::with_advice_map) /
+/// [MemAdviceProvider::get_advice_map](super::MemAdviceProvider::get_advice_map).
+pub fn hash_advice_values(values: &[Felt]) -> Word {
+    let mut state = [Felt::ZERO; STATE_WIDTH];
+
+    // initialize the capacity portion of the state with a domain tag derived from the length of
+    // the input, so that inputs of different lengths never produce colliding digests
+    state[RATE_WIDTH] = Felt::new(values.len() as u64);
+
+    // absorb the input in chunks of RATE_WIDTH elements, permuting the state after each chunk
+    for chunk in values.chunks(RATE_WIDTH) {
+        for (state_elem, &value) in state[..RATE_WIDTH].iter_mut().zip(chunk) {
+            *state_elem += value;
+        }
+        hasher::permute(&mut state);
+    }
+
+    // squeeze out the digest from the first CAPACITY_WIDTH elements of the rate portion
+    let mut digest = [Felt::ZERO; CAPACITY_WIDTH];
+    digest.copy_from_slice(&state[..CAPACITY_WIDTH]);
+    digest
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hash_advice_values_is_deterministic() {
+        let values = vec![Felt::new(1), Felt::new(2), Felt::new(3)];
+        assert_eq!(hash_advice_values(&values), hash_advice_values(&values));
+    }
+
+    #[test]
+    fn hash_advice_values_differs_by_content() {
+        let a = vec![Felt::new(1), Felt::new(2)];
+        let b = vec![Felt::new(1), Felt::new(3)];
+        assert_ne!(hash_advice_values(&a), hash_advice_values(&b));
+    }
+
+    #[test]
+    fn hash_advice_values_differs_by_length() {
+        // the domain tag derived from `values.len()` should keep these from colliding even though
+        // the extra element is the RATE_WIDTH-padding default of zero
+        let short = vec![Felt::new(1); RATE_WIDTH];
+        let long = {
+            let mut values = short.clone();
+            values.push(Felt::ZERO);
+            values
+        };
+        assert_ne!(hash_advice_values(&short), hash_advice_values(&long));
+    }
+
+    #[test]
+    fn hash_advice_values_handles_empty_input() {
+        // must not panic, and must not collide with a non-empty input
+        let empty = hash_advice_values(&[]);
+        let non_empty = hash_advice_values(&[Felt::ZERO]);
+        assert_ne!(empty, non_empty);
+    }
+}
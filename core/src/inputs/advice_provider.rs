@@ -0,0 +1,244 @@
+use super::MerkleStore;
+use crate::{errors::MerkleStoreError, Felt, Word};
+use winter_utils::collections::{BTreeMap, Vec, VecDeque};
+
+// ADVICE PROVIDER
+// ================================================================================================
+
+/// Defines the interface through which the VM requests nondeterministic ("advice") inputs from
+/// its host while a program is executing.
+///
+/// Prior to this, all advice inputs had to be known up front and baked into an immutable
+/// [ProgramInputs](super::ProgramInputs) before a program started running. Implementing this
+/// trait lets a host supply advice lazily: compute it on demand from an external oracle, stream
+/// it in from disk, or simply log every request for debugging, none of which is possible when
+/// the tape is fixed ahead of time.
+///
+/// [MemAdviceProvider] is the default implementation, backed by the same in-memory tape, map,
+/// and set of Merkle trees that [ProgramInputs](super::ProgramInputs) used to own directly.
+pub trait AdviceProvider {
+    /// Removes the next value from the front of the advice tape and returns it.
+    ///
+    /// # Errors
+    /// Returns an error if the advice tape is empty.
+    fn pop_advice(&mut self) -> Result<Felt, AdviceProviderError>;
+
+    /// Returns a reference to the values registered under the specified key in the advice map.
+    ///
+    /// The key is expected to be computed as described in [super::hash_advice_values].
+    fn get_advice_map(&self, key: Word) -> Option<&[Felt]>;
+
+    /// Returns a Merkle path to the node at the specified depth and index in the tree with the
+    /// specified root.
+    ///
+    /// # Errors
+    /// Returns an error if:
+    /// - No Merkle tree with the specified root is registered with this provider.
+    /// - The specified depth or index is not valid for the tree with the specified root.
+    fn get_merkle_path(
+        &self,
+        root: Word,
+        depth: Felt,
+        index: Felt,
+    ) -> Result<Vec<Word>, AdviceProviderError>;
+
+    /// Looks up the advice map entry registered under `key` and pushes its values onto the advice
+    /// tape, in order, so that the next `pop_advice` calls return them before anything already
+    /// queued.
+    ///
+    /// This is meant to be the host-side half of an `AdviceInjector` operation that pulls a
+    /// committed value back in by the digest the VM computed for it (see
+    /// [super::hash_advice_values]): the VM would push a digest onto the stack, then dispatch an
+    /// `AdviceInjector` operation that calls this method with it. That operation and its dispatch
+    /// are not present in this checkout's `operations` module, so today nothing in the VM can
+    /// actually reach this method — it is only exercised directly, by the tests below.
+    ///
+    /// # Errors
+    /// Returns an error if no entry is registered under `key`.
+    fn push_advice_map_values(&mut self, key: Word) -> Result<(), AdviceProviderError>;
+}
+
+// ADVICE PROVIDER ERROR
+// ================================================================================================
+
+/// Describes the ways in which an [AdviceProvider] can fail to satisfy a request made by the VM.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AdviceProviderError {
+    /// The advice tape was empty when a value was requested from it.
+    AdviceTapeEmpty,
+    /// A Merkle path request could not be satisfied by the backing [MerkleStore].
+    InvalidMerkleRequest(MerkleStoreError),
+    /// No advice map entry was registered under the requested key.
+    AdviceMapKeyNotFound(Word),
+}
+
+// MEM ADVICE PROVIDER
+// ================================================================================================
+
+/// An [AdviceProvider] backed by an in-memory advice tape, a key-value advice map, and a
+/// [MerkleStore], all of which are supplied up front.
+///
+/// This is the provider used by [ProgramInputs](super::ProgramInputs); it reproduces the
+/// fixed-tape behavior the VM had before advice input was abstracted behind a trait.
+///
+/// The tape is a [VecDeque] rather than a [Vec] so that both ends of a full-tape consumption stay
+/// cheap: [AdviceProvider::pop_advice] is a `pop_front` and [AdviceProvider::push_advice_map_values]
+/// is a handful of `push_front`s, both O(1) (amortized), instead of the O(n) shift a `Vec::remove(0)`
+/// / `Vec::splice(0..0, ..)` pair would do on every call — which would make draining an n-value tape
+/// O(n^2) overall.
+#[derive(Clone, Debug, Default)]
+pub struct MemAdviceProvider {
+    advice_tape: VecDeque<Felt>,
+    advice_map: BTreeMap<[u8; 32], Vec<Felt>>,
+    merkle_store: MerkleStore,
+}
+
+impl MemAdviceProvider {
+    /// Returns a new [MemAdviceProvider] instantiated with the specified advice tape, advice map,
+    /// and Merkle store.
+    pub fn new(
+        advice_tape: Vec<Felt>,
+        advice_map: BTreeMap<[u8; 32], Vec<Felt>>,
+        merkle_store: MerkleStore,
+    ) -> Self {
+        Self {
+            advice_tape: advice_tape.into(),
+            advice_map,
+            merkle_store,
+        }
+    }
+
+    /// Returns a reference to the advice tape.
+    pub fn advice_tape(&self) -> &VecDeque<Felt> {
+        &self.advice_tape
+    }
+
+    /// Returns a reference to the advice map.
+    pub fn advice_map(&self) -> &BTreeMap<[u8; 32], Vec<Felt>> {
+        &self.advice_map
+    }
+
+    /// Returns a reference to the [MerkleStore] backing this provider.
+    pub fn merkle_store(&self) -> &MerkleStore {
+        &self.merkle_store
+    }
+
+    /// Returns a mutable reference to the [MerkleStore] backing this provider, e.g. to apply a
+    /// leaf update before a subsequent Merkle path request.
+    pub fn merkle_store_mut(&mut self) -> &mut MerkleStore {
+        &mut self.merkle_store
+    }
+}
+
+impl AdviceProvider for MemAdviceProvider {
+    fn pop_advice(&mut self) -> Result<Felt, AdviceProviderError> {
+        if self.advice_tape.is_empty() {
+            return Err(AdviceProviderError::AdviceTapeEmpty);
+        }
+
+        // the tape is built front-to-back (see `ProgramInputs::with_advice_map`), so the next
+        // value to supply to the VM is the one at the front, not the one most recently pushed
+        Ok(self.advice_tape.pop_front().expect("checked non-empty above"))
+    }
+
+    fn get_advice_map(&self, key: Word) -> Option<&[Felt]> {
+        self.advice_map
+            .get(&super::IntoBytes::into_bytes(key))
+            .map(|values| values.as_slice())
+    }
+
+    fn get_merkle_path(
+        &self,
+        root: Word,
+        depth: Felt,
+        index: Felt,
+    ) -> Result<Vec<Word>, AdviceProviderError> {
+        use crate::StarkField;
+
+        self.merkle_store
+            .get_path(root, depth.as_int() as u8, index.as_int())
+            .map_err(AdviceProviderError::InvalidMerkleRequest)
+    }
+
+    fn push_advice_map_values(&mut self, key: Word) -> Result<(), AdviceProviderError> {
+        let values = self
+            .get_advice_map(key)
+            .ok_or(AdviceProviderError::AdviceMapKeyNotFound(key))?
+            .to_vec();
+
+        // push each value onto the front in reverse so that, once all are pushed, they sit ahead
+        // of the rest of the tape in their original order; each push_front is O(1) amortized
+        for &value in values.iter().rev() {
+            self.advice_tape.push_front(value);
+        }
+        Ok(())
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pop_advice_returns_values_in_tape_order() {
+        let tape = vec![Felt::new(1), Felt::new(2), Felt::new(3)];
+        let mut provider = MemAdviceProvider::new(tape, BTreeMap::new(), MerkleStore::new());
+
+        assert_eq!(provider.pop_advice(), Ok(Felt::new(1)));
+        assert_eq!(provider.pop_advice(), Ok(Felt::new(2)));
+        assert_eq!(provider.pop_advice(), Ok(Felt::new(3)));
+        assert_eq!(provider.pop_advice(), Err(AdviceProviderError::AdviceTapeEmpty));
+    }
+
+    #[test]
+    fn push_advice_map_values_queues_values_ahead_of_the_tape() {
+        let key = [Felt::new(1), Felt::new(2), Felt::new(3), Felt::new(4)];
+        let mut advice_map = BTreeMap::new();
+        advice_map.insert(
+            super::super::IntoBytes::into_bytes(key),
+            vec![Felt::new(42)],
+        );
+
+        let tape = vec![Felt::new(1)];
+        let mut provider = MemAdviceProvider::new(tape, advice_map, MerkleStore::new());
+
+        provider.push_advice_map_values(key).unwrap();
+        assert_eq!(provider.pop_advice(), Ok(Felt::new(42)));
+        assert_eq!(provider.pop_advice(), Ok(Felt::new(1)));
+    }
+
+    #[test]
+    fn push_advice_map_values_reports_missing_key() {
+        let mut provider = MemAdviceProvider::default();
+        let key = [Felt::new(9); 4];
+        assert_eq!(
+            provider.push_advice_map_values(key),
+            Err(AdviceProviderError::AdviceMapKeyNotFound(key))
+        );
+    }
+
+    /// Exercises the host-side commit-by-digest flow in isolation: a program commits to a secret
+    /// value by its [super::super::hash_advice_values] digest, and later supplies that same digest (e.g.
+    /// as the output of a `hash` instruction) to pull the value back onto the tape. There is no
+    /// `AdviceInjector` operation or VM dispatch in this checkout to drive `push_advice_map_values`
+    /// from a digest the program computed at runtime, so this test calls it directly instead of
+    /// going through a VM; the digest-on-the-stack -> operation -> this call path is not delivered.
+    #[test]
+    fn push_advice_map_values_round_trips_a_committed_value_by_its_digest() {
+        let secret = vec![Felt::new(11), Felt::new(22), Felt::new(33)];
+        let digest = super::super::hash_advice_values(&secret);
+
+        let mut advice_map = BTreeMap::new();
+        advice_map.insert(super::super::IntoBytes::into_bytes(digest), secret.clone());
+        let mut provider = MemAdviceProvider::new(Vec::new(), advice_map, MerkleStore::new());
+
+        provider.push_advice_map_values(digest).unwrap();
+        for &expected in secret.iter() {
+            assert_eq!(provider.pop_advice(), Ok(expected));
+        }
+        assert_eq!(provider.pop_advice(), Err(AdviceProviderError::AdviceTapeEmpty));
+    }
+}
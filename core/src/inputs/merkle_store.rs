@@ -0,0 +1,302 @@
+use super::{hasher, AdviceSet, IntoBytes, Word};
+use crate::errors::MerkleStoreError;
+use winter_utils::collections::{BTreeMap, Vec};
+
+// MERKLE STORE
+// ================================================================================================
+
+/// A store of Merkle tree nodes, keyed by node hash rather than by tree root.
+///
+/// The previous model kept one `AdviceSet` per root in a `BTreeMap<[u8; 32], AdviceSet>`: trees
+/// were immutable, two trees could never share a root, and there was no way to update a leaf
+/// without throwing the whole tree away and rebuilding it. [MerkleStore] instead stores individual
+/// nodes as `node_hash -> (left_child, right_child)` in a single flat map. This means:
+/// - Multiple trees, including ones which share subtrees, coexist in the same store for free.
+/// - Two unrelated trees with the same root hash no longer conflict, because there is nothing
+///   keyed by "the" root of a tree; any hash can be used as a root to traverse from.
+/// - [MerkleStore::update_leaf] can replace a single leaf and rehash only the `O(depth)` nodes on
+///   the path to the root, returning the new root, instead of rebuilding the tree from scratch.
+#[derive(Clone, Debug, Default)]
+pub struct MerkleStore {
+    nodes: BTreeMap<[u8; 32], (Word, Word)>,
+}
+
+impl MerkleStore {
+    // CONSTRUCTORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns a new, empty [MerkleStore].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a new [MerkleStore] populated with the leaves of `sets`.
+    ///
+    /// # Errors
+    /// Returns an error if the advice sets overlap such that the same node hash is recorded with
+    /// two different pairs of children, which would indicate a hash collision or a corrupted set.
+    pub fn from_advice_sets<'a>(
+        sets: impl IntoIterator<Item = &'a AdviceSet>,
+    ) -> Result<Self, MerkleStoreError> {
+        let mut store = Self::new();
+        for set in sets {
+            store.add_merkle_tree(set)?;
+        }
+        Ok(store)
+    }
+
+    /// Returns a [MerkleStore] containing a sparse Merkle tree of the specified `depth`, with
+    /// `entries` placed at the given leaf indexes and every other leaf defaulting to
+    /// `Word::default()`.
+    ///
+    /// Rather than materializing every one of the `2^depth` leaves, the hashes of the empty
+    /// subtrees at each level are computed once (there are only `depth` distinct empty-subtree
+    /// hashes), recorded in the store once each, and reused for every leaf which was not
+    /// explicitly provided. This keeps storage at `O(depth * (entries.len() + 1))` rather than
+    /// `O(2^depth)`, which matters for something like a depth-64 tree of mostly-empty leaves.
+    ///
+    /// Returns the populated [MerkleStore] together with the resulting tree root.
+    ///
+    /// # Errors
+    /// Returns an error if `depth` is 0 or any index in `entries` is out of range for `depth`.
+    pub fn new_sparse_tree(
+        depth: u8,
+        entries: BTreeMap<u64, Word>,
+    ) -> Result<(Self, Word), MerkleStoreError> {
+        if depth == 0 {
+            return Err(MerkleStoreError::InvalidDepth(depth));
+        }
+        // `1u64 << depth` overflows for `depth == 64` (the shift amount equals the type's bit
+        // width); `checked_shl` turns that into `None` instead of panicking, and `None` means
+        // every `u64` index is in range, so there is nothing left to validate against
+        let max_index = 1u64.checked_shl(depth as u32);
+        if let Some(max_index) = max_index {
+            for &index in entries.keys() {
+                if index >= max_index {
+                    return Err(MerkleStoreError::InvalidIndex(depth, index));
+                }
+            }
+        }
+
+        // precompute the hash of an empty subtree at every level, from leaves up to the root, and
+        // record each one in the store so that paths into empty subtrees can be traversed
+        let mut store = Self::new();
+        let mut empty_hashes = Vec::with_capacity(depth as usize + 1);
+        empty_hashes.push(Word::default());
+        for _ in 0..depth {
+            let prev = *empty_hashes.last().expect("always at least one entry");
+            let node = hasher::merge(&[prev, prev]);
+            store.nodes.insert(node.into_bytes(), (prev, prev));
+            empty_hashes.push(node);
+        }
+
+        let mut level: BTreeMap<u64, Word> = entries;
+        for level_depth in (1..=depth).rev() {
+            let empty_child = empty_hashes[(depth - level_depth) as usize];
+            let mut parents: BTreeMap<u64, Word> = BTreeMap::new();
+
+            for (&index, &value) in level.iter() {
+                let parent_index = index / 2;
+                if parents.contains_key(&parent_index) {
+                    continue;
+                }
+
+                let sibling_index = index ^ 1;
+                let sibling = level.get(&sibling_index).copied().unwrap_or(empty_child);
+                let (left, right) = if index % 2 == 0 {
+                    (value, sibling)
+                } else {
+                    (sibling, value)
+                };
+
+                let parent = hasher::merge(&[left, right]);
+                store.nodes.insert(parent.into_bytes(), (left, right));
+                parents.insert(parent_index, parent);
+            }
+
+            level = parents;
+        }
+
+        let root = level
+            .remove(&0)
+            .unwrap_or_else(|| empty_hashes[depth as usize]);
+
+        Ok((store, root))
+    }
+
+    // PUBLIC ACCESSORS
+    // --------------------------------------------------------------------------------------------
+
+    /// Returns true if this store holds no nodes.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Returns the children of the node with the specified hash, if it is recorded in this store.
+    pub fn get_node(&self, node: Word) -> Option<(Word, Word)> {
+        self.nodes.get(&node.into_bytes()).copied()
+    }
+
+    /// Returns the value of the leaf at `index` in the tree of the given `depth` rooted at `root`.
+    ///
+    /// # Errors
+    /// Returns an error if any node on the path from `root` to the leaf is missing from the store.
+    pub fn get_leaf(&self, root: Word, depth: u8, index: u64) -> Result<Word, MerkleStoreError> {
+        self.traverse(root, depth, index).map(|(leaf, _)| leaf)
+    }
+
+    /// Returns the Merkle path from the leaf at `index` in the tree of the given `depth` rooted
+    /// at `root` up to (but not including) the root.
+    ///
+    /// # Errors
+    /// Returns an error if any node on the path from `root` to the leaf is missing from the store.
+    pub fn get_path(
+        &self,
+        root: Word,
+        depth: u8,
+        index: u64,
+    ) -> Result<Vec<Word>, MerkleStoreError> {
+        let (_, path) = self.traverse(root, depth, index)?;
+        Ok(path)
+    }
+
+    /// Replaces the leaf at `index` in the tree of the given `depth` rooted at `root` with
+    /// `new_value` and returns the new root.
+    ///
+    /// Only the `O(depth)` nodes on the path from the leaf to the root are rehashed; the rest of
+    /// the tree, including any other leaves sharing siblings with the updated path, is left
+    /// untouched in the store.
+    ///
+    /// # Errors
+    /// Returns an error if any node on the path from `root` to the leaf is missing from the store.
+    pub fn update_leaf(
+        &mut self,
+        root: Word,
+        depth: u8,
+        index: u64,
+        new_value: Word,
+    ) -> Result<Word, MerkleStoreError> {
+        let path = self.get_path(root, depth, index)?;
+
+        let mut node = new_value;
+        let mut node_index = index;
+        for sibling in path {
+            let (left, right) = if node_index % 2 == 0 {
+                (node, sibling)
+            } else {
+                (sibling, node)
+            };
+            node = hasher::merge(&[left, right]);
+            self.nodes.insert(node.into_bytes(), (left, right));
+            node_index /= 2;
+        }
+
+        Ok(node)
+    }
+
+    // HELPERS
+    // --------------------------------------------------------------------------------------------
+
+    /// Walks the tree of the given `depth` rooted at `root` down to the leaf at `index`, returning
+    /// the leaf value together with the Merkle path (siblings, ordered from the leaf upward).
+    fn traverse(
+        &self,
+        root: Word,
+        depth: u8,
+        index: u64,
+    ) -> Result<(Word, Vec<Word>), MerkleStoreError> {
+        let mut path = Vec::with_capacity(depth as usize);
+        let mut node = root;
+
+        // bits of `index`, from the one closest to the root down to the leaf
+        for bit_pos in (0..depth).rev() {
+            let (left, right) = self
+                .get_node(node)
+                .ok_or(MerkleStoreError::NodeNotFound(node))?;
+
+            let bit = (index >> bit_pos) & 1;
+            let (next, sibling) = if bit == 0 { (left, right) } else { (right, left) };
+
+            path.push(sibling);
+            node = next;
+        }
+
+        path.reverse();
+        Ok((node, path))
+    }
+
+    /// Absorbs every inner node of `set` into this store.
+    ///
+    /// `set` already maintains every inner node it has ever hashed (that's how it answers
+    /// `get_node`/`get_path` in the first place), so this just copies that existing `O(2^depth)`
+    /// layer of nodes across via [AdviceSet::inner_nodes] rather than rederiving it by walking
+    /// every leaf's root path one at a time, which redundantly rehashes shared ancestors `2^depth`
+    /// times over.
+    ///
+    /// # Errors
+    /// Returns an error if a node hash already recorded in this store (from an earlier call to
+    /// this method) is recorded again here with a different pair of children, which would
+    /// indicate a hash collision or a corrupted set.
+    fn add_merkle_tree(&mut self, set: &AdviceSet) -> Result<(), MerkleStoreError> {
+        for (node, left, right) in set.inner_nodes() {
+            match self.nodes.insert(node.into_bytes(), (left, right)) {
+                Some(existing) if existing != (left, right) => {
+                    return Err(MerkleStoreError::ConflictingNode(node));
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leaf(value: u64) -> Word {
+        [Felt::new(value), Felt::new(0), Felt::new(0), Felt::new(0)]
+    }
+
+    #[test]
+    fn new_sparse_tree_at_depth_64_does_not_panic() {
+        let mut entries = BTreeMap::new();
+        entries.insert(0u64, leaf(1));
+        entries.insert(u64::MAX, leaf(2));
+
+        let (store, root) = MerkleStore::new_sparse_tree(64, entries).unwrap();
+        assert_eq!(store.get_leaf(root, 64, 0).unwrap(), leaf(1));
+        assert_eq!(store.get_leaf(root, 64, u64::MAX).unwrap(), leaf(2));
+    }
+
+    #[test]
+    fn new_sparse_tree_rejects_out_of_range_index() {
+        let mut entries = BTreeMap::new();
+        entries.insert(4u64, leaf(1));
+
+        let err = MerkleStore::new_sparse_tree(2, entries).unwrap_err();
+        assert_eq!(err, MerkleStoreError::InvalidIndex(2, 4));
+    }
+
+    #[test]
+    fn get_path_and_update_leaf_round_trip() {
+        let mut entries = BTreeMap::new();
+        entries.insert(0u64, leaf(1));
+        entries.insert(1u64, leaf(2));
+        entries.insert(2u64, leaf(3));
+        entries.insert(3u64, leaf(4));
+
+        let (mut store, root) = MerkleStore::new_sparse_tree(2, entries).unwrap();
+        let path = store.get_path(root, 2, 1).unwrap();
+
+        let new_root = store.update_leaf(root, 2, 1, leaf(42)).unwrap();
+        assert_eq!(store.get_leaf(new_root, 2, 1).unwrap(), leaf(42));
+        // updating a leaf shouldn't change its own sibling path
+        assert_eq!(store.get_path(new_root, 2, 1).unwrap(), path);
+        assert_eq!(store.get_leaf(new_root, 2, 0).unwrap(), leaf(1));
+    }
+}
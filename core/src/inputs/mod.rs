@@ -5,11 +5,28 @@ use super::{
     Felt, FieldElement, Word,
 };
 use core::convert::TryInto;
-use winter_utils::collections::{BTreeMap, Vec};
+use winter_utils::collections::{BTreeMap, Vec, VecDeque};
 
 mod advice;
 pub use advice::AdviceSet;
 
+mod advice_provider;
+pub use advice_provider::{AdviceProvider, AdviceProviderError, MemAdviceProvider};
+
+mod advice_map_hasher;
+pub use advice_map_hasher::hash_advice_values;
+
+mod merkle_store;
+pub use merkle_store::MerkleStore;
+
+// gated on a `serde` feature and `serde`/`serde_json` dependencies that this checkout has no
+// Cargo.toml anywhere to declare; until one exists, this module is reachable only by crates that
+// happen to depend on a `vm_core` built elsewhere with those features already on
+#[cfg(all(feature = "std", feature = "serde"))]
+mod serde_format;
+#[cfg(all(feature = "std", feature = "serde"))]
+pub use serde_format::{AdviceMapEntryFile, AdviceSetFile, InputFile};
+
 // PROGRAM INPUTS
 // ================================================================================================
 
@@ -27,12 +44,15 @@ pub use advice::AdviceSet;
 ///    inputs for instructions which work with Merkle trees.
 ///
 /// TODO: add more detailed explanation.
+///
+/// Internally, all nondeterministic ("advice") inputs are held by a [MemAdviceProvider], the
+/// default [AdviceProvider] implementation. Hosts which need to supply advice lazily rather than
+/// up front (e.g., to stream it in from an oracle) can implement [AdviceProvider] directly instead
+/// of going through [ProgramInputs].
 #[derive(Clone, Debug)]
 pub struct ProgramInputs {
     stack_init: Vec<Felt>,
-    advice_tape: Vec<Felt>,
-    advice_map: BTreeMap<[u8; 32], Vec<Felt>>,
-    advice_sets: BTreeMap<[u8; 32], AdviceSet>,
+    advice_provider: MemAdviceProvider,
 }
 
 impl ProgramInputs {
@@ -49,13 +69,12 @@ impl ProgramInputs {
     /// Returns an error if:
     /// - The number initial stack values is greater than 16.
     /// - Any of the initial stack values or the advice tape values are not valid field elements.
-    /// - Any of the advice sets have the same root.
     pub fn new(
         stack_init: &[u64],
         advice_tape: &[u64],
         advice_sets: Vec<AdviceSet>,
     ) -> Result<Self, InputError> {
-        Self::with_advice_map(stack_init, advice_tape, BTreeMap::new(), advice_sets)
+        Self::with_advice_map(stack_init, advice_tape, Vec::new(), advice_sets)
     }
 
     /// Returns [ProgramInputs] instantiated with the specified initial stack values, advice tape,
@@ -65,15 +84,25 @@ impl ProgramInputs {
     /// the stack one by one. The result of this is that the last value in the `stack_init` slice
     /// will end up at the top of the stack.
     ///
+    /// Each entry of `advice_map` is a `(key, values)` pair where `key` must equal
+    /// [hash_advice_values] of `values` — the RPO digest a program computes at runtime (e.g. as
+    /// the output of a `hash` instruction) in order to pull the matching entry back off the advice
+    /// map. Passing a `key` that doesn't match its `values` is rejected rather than silently
+    /// recomputed, so that a typo'd key fails fast at input-construction time instead of at the
+    /// first (failed) VM lookup.
+    ///
+    /// Unlike the advice sets of old, `advice_sets` may freely include multiple trees that share a
+    /// root or overlapping subtrees; all of their nodes are merged into a single [MerkleStore].
+    ///
     /// # Errors
     /// Returns an error if:
     /// - The number initial stack values is greater than 16.
     /// - Any of the initial stack values or the advice tape values are not valid field elements.
-    /// - Any of the advice sets have the same root.
+    /// - Any `advice_map` entry's key does not match the RPO digest of its values.
     pub fn with_advice_map(
         stack_init: &[u64],
         advice_tape: &[u64],
-        advice_map: BTreeMap<[u8; 32], Vec<Felt>>,
+        advice_map: Vec<(Word, Vec<Felt>)>,
         advice_sets: Vec<AdviceSet>,
     ) -> Result<Self, InputError> {
         // convert initial stack values into field elements
@@ -94,20 +123,27 @@ impl ProgramInputs {
             advice_tape_elements.push(element);
         }
 
-        // put advice sets into a map
-        let mut advice_sets_elements = BTreeMap::new();
-        for advice_set in advice_sets {
-            let key = advice_set.root().into_bytes();
-            if advice_sets_elements.insert(key, advice_set).is_some() {
-                return Err(InputError::DuplicateAdviceRoot(key));
-            };
+        // verify each advice map entry is keyed by the RPO digest of its own values
+        let mut advice_map_elements = BTreeMap::new();
+        for (key, values) in advice_map {
+            let expected_key = hash_advice_values(&values);
+            if key != expected_key {
+                return Err(InputError::AdviceMapKeyMismatch(expected_key.into_bytes()));
+            }
+            advice_map_elements.insert(key.into_bytes(), values);
         }
 
+        // merge every advice set's nodes into a single Merkle store
+        let merkle_store = MerkleStore::from_advice_sets(advice_sets.iter())
+            .map_err(InputError::MerkleStoreError)?;
+
         Ok(Self {
             stack_init: init_stack_elements,
-            advice_tape: advice_tape_elements,
-            advice_map,
-            advice_sets: advice_sets_elements,
+            advice_provider: MemAdviceProvider::new(
+                advice_tape_elements,
+                advice_map_elements,
+                merkle_store,
+            ),
         })
     }
 
@@ -130,9 +166,7 @@ impl ProgramInputs {
     pub fn none() -> Self {
         Self {
             stack_init: Vec::new(),
-            advice_tape: Vec::new(),
-            advice_map: BTreeMap::new(),
-            advice_sets: BTreeMap::new(),
+            advice_provider: MemAdviceProvider::default(),
         }
     }
 
@@ -145,30 +179,32 @@ impl ProgramInputs {
     }
 
     /// Returns a reference to the advice tape.
-    pub fn advice_tape(&self) -> &[Felt] {
-        &self.advice_tape
+    pub fn advice_tape(&self) -> &VecDeque<Felt> {
+        self.advice_provider.advice_tape()
+    }
+
+    /// Returns a reference to the [MemAdviceProvider] backing these [ProgramInputs].
+    pub fn advice_provider(&self) -> &MemAdviceProvider {
+        &self.advice_provider
+    }
+
+    /// Returns a reference to the values registered in the advice map under `key`, where `key` is
+    /// the RPO digest of those values as computed by [hash_advice_values].
+    pub fn get_mapped_values(&self, key: &Word) -> Option<&[Felt]> {
+        self.advice_provider.get_advice_map(*key)
     }
 
     // DESTRUCTURING
     // --------------------------------------------------------------------------------------------
 
-    /// Decomposes these [ProgramInputs] into their raw components.
-    #[allow(clippy::type_complexity)]
-    pub fn into_parts(
-        self,
-    ) -> (
-        Vec<Felt>,
-        Vec<Felt>,
-        BTreeMap<[u8; 32], Vec<Felt>>,
-        BTreeMap<[u8; 32], AdviceSet>,
-    ) {
+    /// Decomposes these [ProgramInputs] into their raw components: the initial stack values and
+    /// the [MemAdviceProvider] which supplies the program's nondeterministic inputs.
+    pub fn into_parts(self) -> (Vec<Felt>, MemAdviceProvider) {
         let Self {
             stack_init,
-            advice_tape,
-            advice_map,
-            advice_sets,
+            advice_provider,
         } = self;
 
-        (stack_init, advice_tape, advice_map, advice_sets)
+        (stack_init, advice_provider)
     }
 }
@@ -0,0 +1,260 @@
+use super::{hash_advice_values, AdviceSet, ProgramInputs};
+use crate::{errors::InputError, Felt, FieldElement, StarkField, Word};
+use core::convert::TryInto;
+use serde::{Deserialize, Serialize};
+use std::io;
+use winter_utils::collections::Vec;
+
+// INPUT FILE SCHEMA
+// ================================================================================================
+
+/// The on-disk representation of [ProgramInputs], read and written via
+/// [ProgramInputs::from_reader] / [ProgramInputs::to_writer].
+///
+/// This gives toolchains (the CLI, benchmarks, test fixtures) a stable, reviewable way to author
+/// proving inputs in JSON rather than hard-coding them as Rust literals, e.g. in place of
+/// `ProgramInputs::from_stack_inputs(&[0, 1])`. Because [InputFile] only derives `serde`'s
+/// `Serialize`/`Deserialize` (no JSON-specific behavior), the same schema can also be loaded from
+/// TOML or any other self-describing format by calling `toml::from_str::<InputFile>` directly;
+/// only `from_reader`/`to_writer` themselves are JSON-specific.
+///
+/// # Example (JSON)
+/// ```json
+/// {
+///   "stack_init": [0, 1],
+///   "advice_tape": [42],
+///   "advice_map": [
+///     { "key": "0x1e2f3a4b...", "values": [1, 2, 3, 4] }
+///   ],
+///   "advice_sets": [
+///     { "leaves": [[1, 2, 3, 4], [5, 6, 7, 8]] }
+///   ]
+/// }
+/// ```
+#[derive(Serialize, Deserialize)]
+pub struct InputFile {
+    /// Initial stack values, deepest first. See [ProgramInputs::new].
+    pub stack_init: Vec<u64>,
+    /// Values placed on the advice tape. See [ProgramInputs::new].
+    pub advice_tape: Vec<u64>,
+    /// Advice map entries, keyed by the hex-encoded RPO digest of their values. The key is
+    /// informational only: it is recomputed via [super::hash_advice_values] on load, and the
+    /// recomputed key is what [ProgramInputs::with_advice_map] then checks against the values.
+    #[serde(default)]
+    pub advice_map: Vec<AdviceMapEntryFile>,
+    /// Merkle trees to register with the resulting [ProgramInputs]' [MerkleStore](super::MerkleStore).
+    #[serde(default)]
+    pub advice_sets: Vec<AdviceSetFile>,
+}
+
+/// One entry of the `advice_map` field of an [InputFile].
+#[derive(Serialize, Deserialize)]
+pub struct AdviceMapEntryFile {
+    /// Hex-encoded digest of `values`, for human readability; not trusted on load.
+    pub key: String,
+    pub values: Vec<u64>,
+}
+
+/// Describes a single Merkle tree to reconstruct into an [AdviceSet].
+///
+/// A tree can be authored either as a full list of leaves, or as a single precomputed Merkle path
+/// when only one branch of a much larger tree is relevant to the program being proven.
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum AdviceSetFile {
+    /// Reconstructs the tree from a dense list of leaf values.
+    Leaves { leaves: Vec<[u64; 4]> },
+    /// Reconstructs (a single branch of) the tree from a known path to one leaf.
+    Path {
+        depth: u32,
+        index: u64,
+        leaf: [u64; 4],
+        path: Vec<[u64; 4]>,
+    },
+}
+
+impl ProgramInputs {
+    // SERIALIZATION
+    // --------------------------------------------------------------------------------------------
+
+    /// Reads an [InputFile] in JSON format from `reader` and converts it into [ProgramInputs].
+    ///
+    /// # Errors
+    /// Returns an error if the file is not well-formed, if any stack, advice tape, or advice map
+    /// value is not a valid field element (see [InputError::NotFieldElement]), or if an advice set
+    /// cannot be reconstructed from the given leaves or path.
+    pub fn from_reader<R: io::Read>(reader: R) -> Result<Self, InputError> {
+        let file: InputFile =
+            serde_json::from_reader(reader).map_err(|_| InputError::InvalidInputFile)?;
+
+        let mut advice_map = Vec::with_capacity(file.advice_map.len());
+        for entry in file.advice_map {
+            let mut values = Vec::with_capacity(entry.values.len());
+            for value in entry.values {
+                let element: Felt = value
+                    .try_into()
+                    .map_err(|_| InputError::NotFieldElement(value, "advice map value"))?;
+                values.push(element);
+            }
+            // the file's `key` field is informational only (see `AdviceMapEntryFile::key`); the
+            // key `with_advice_map` actually validates against is always recomputed from `values`
+            let key = hash_advice_values(&values);
+            advice_map.push((key, values));
+        }
+
+        let advice_sets = file
+            .advice_sets
+            .into_iter()
+            .map(advice_set_from_file)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Self::with_advice_map(&file.stack_init, &file.advice_tape, advice_map, advice_sets)
+    }
+
+    /// Writes these [ProgramInputs] out as an [InputFile] in JSON format.
+    ///
+    /// This is the inverse of [ProgramInputs::from_reader]; the advice map keys written out are
+    /// the RPO digests already computed when these [ProgramInputs] were built.
+    ///
+    /// # Errors
+    /// Returns [InputError::UnsupportedAdviceSetsRoundTrip] if these [ProgramInputs] carry a
+    /// non-empty [MerkleStore](super::MerkleStore): once sets are merged into the store, it no
+    /// longer tracks which nodes came from which original tree, so there is nothing correct to
+    /// write back out as `advice_sets`. Writing an empty `advice_sets` array in that case would
+    /// silently produce a file that cannot reconstruct these [ProgramInputs].
+    pub fn to_writer<W: io::Write>(&self, writer: W) -> Result<(), InputError> {
+        if !self.advice_provider().merkle_store().is_empty() {
+            return Err(InputError::UnsupportedAdviceSetsRoundTrip);
+        }
+
+        // `stack_init()` is stored top-first, while the file lists stack values deepest first
+        let stack_init = self
+            .stack_init()
+            .iter()
+            .rev()
+            .map(|&felt| felt_to_u64(felt))
+            .collect();
+
+        let advice_tape = self
+            .advice_tape()
+            .iter()
+            .map(|&felt| felt_to_u64(felt))
+            .collect();
+
+        let advice_map = self
+            .advice_provider()
+            .advice_map()
+            .iter()
+            .map(|(key, values)| AdviceMapEntryFile {
+                key: hex_encode(key),
+                values: values.iter().map(|&felt| felt_to_u64(felt)).collect(),
+            })
+            .collect();
+
+        let file = InputFile {
+            stack_init,
+            advice_tape,
+            advice_map,
+            // checked above: the merkle store backing these inputs is empty, so there is nothing
+            // to write here
+            advice_sets: Vec::new(),
+        };
+
+        serde_json::to_writer_pretty(writer, &file).map_err(|_| InputError::InvalidInputFile)
+    }
+}
+
+fn advice_set_from_file(file: AdviceSetFile) -> Result<AdviceSet, InputError> {
+    match file {
+        AdviceSetFile::Leaves { leaves } => {
+            let leaves = leaves
+                .into_iter()
+                .map(word_from_u64s)
+                .collect::<Result<Vec<Word>, _>>()?;
+            AdviceSet::new_merkle_tree(leaves).map_err(InputError::AdviceSetError)
+        }
+        AdviceSetFile::Path {
+            depth,
+            index,
+            leaf,
+            path,
+        } => {
+            let leaf = word_from_u64s(leaf)?;
+            let path = path
+                .into_iter()
+                .map(word_from_u64s)
+                .collect::<Result<Vec<Word>, _>>()?;
+            AdviceSet::from_path(depth, index, leaf, path).map_err(InputError::AdviceSetError)
+        }
+    }
+}
+
+fn word_from_u64s(values: [u64; 4]) -> Result<Word, InputError> {
+    let mut word = [Felt::ZERO; 4];
+    for (slot, value) in word.iter_mut().zip(values) {
+        *slot = value
+            .try_into()
+            .map_err(|_| InputError::NotFieldElement(value, "advice set leaf"))?;
+    }
+    Ok(word)
+}
+
+fn felt_to_u64(felt: Felt) -> u64 {
+    felt.as_int()
+}
+
+fn hex_encode(bytes: &[u8; 32]) -> String {
+    let mut s = std::string::String::with_capacity(2 + bytes.len() * 2);
+    s.push_str("0x");
+    for byte in bytes {
+        s.push_str(&std::format!("{:02x}", byte));
+    }
+    s
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::inputs::hash_advice_values;
+
+    #[test]
+    fn to_writer_then_from_reader_round_trips_stack_tape_and_advice_map() {
+        let values = vec![Felt::new(7), Felt::new(8)];
+        let key = hash_advice_values(&values);
+        let original =
+            ProgramInputs::with_advice_map(&[1, 2], &[3, 4], vec![(key, values)], Vec::new())
+                .unwrap();
+
+        let mut buf = Vec::new();
+        original.to_writer(&mut buf).unwrap();
+        let reloaded = ProgramInputs::from_reader(buf.as_slice()).unwrap();
+
+        assert_eq!(original.stack_init(), reloaded.stack_init());
+        assert_eq!(original.advice_tape(), reloaded.advice_tape());
+        assert_eq!(
+            original.get_mapped_values(&key),
+            reloaded.get_mapped_values(&key)
+        );
+    }
+
+    #[test]
+    fn from_reader_rejects_malformed_json() {
+        let err = ProgramInputs::from_reader("not json".as_bytes()).unwrap_err();
+        assert!(matches!(err, InputError::InvalidInputFile));
+    }
+
+    #[test]
+    fn to_writer_rejects_inputs_carrying_advice_sets() {
+        let leaves = vec![[Felt::new(1), Felt::ZERO, Felt::ZERO, Felt::ZERO]; 2];
+        let advice_set = AdviceSet::new_merkle_tree(leaves).unwrap();
+        let inputs =
+            ProgramInputs::with_advice_map(&[1], &[], Vec::new(), vec![advice_set]).unwrap();
+
+        let mut buf = Vec::new();
+        let err = inputs.to_writer(&mut buf).unwrap_err();
+        assert!(matches!(err, InputError::UnsupportedAdviceSetsRoundTrip));
+    }
+}
@@ -15,6 +15,9 @@ use utils::range;
 
 pub mod errors;
 
+#[cfg(feature = "std")]
+pub mod trace;
+
 // TYPE ALIASES
 // ================================================================================================
 
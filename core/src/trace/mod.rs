@@ -0,0 +1,223 @@
+use super::{Felt, FieldElement, AUX_TRACE_RANGE, STACK_TRACE_RANGE, SYS_TRACE_RANGE, TRACE_WIDTH};
+use core::ops::Range;
+use winter_utils::collections::Vec;
+
+// TRACE FRAGMENT
+// ================================================================================================
+
+/// A writable view into one disjoint, contiguous range of columns of the execution trace.
+///
+/// [TraceFragment]s are produced by [TraceBuilder::fragments] so that the sys, stack, and
+/// auxiliary-table column groups of the trace (see the `*_TRACE_RANGE` constants in
+/// [crate](super)) can each be handed to a separate worker thread with no possibility of two
+/// threads aliasing the same column.
+pub struct TraceFragment<'a> {
+    offset: usize,
+    columns: Vec<&'a mut [Felt]>,
+}
+
+impl<'a> TraceFragment<'a> {
+    /// Returns the index, within the full trace, of this fragment's first column.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the number of columns in this fragment.
+    pub fn width(&self) -> usize {
+        self.columns.len()
+    }
+
+    /// Returns the number of rows in this fragment.
+    pub fn len(&self) -> usize {
+        self.columns.first().map(|column| column.len()).unwrap_or(0)
+    }
+
+    /// Returns true if this fragment contains no columns.
+    pub fn is_empty(&self) -> bool {
+        self.columns.is_empty()
+    }
+
+    /// Writes `value` into the column at `col_idx` (relative to this fragment, not the full
+    /// trace) and row `row_idx`.
+    pub fn set(&mut self, col_idx: usize, row_idx: usize, value: Felt) {
+        self.columns[col_idx][row_idx] = value;
+    }
+}
+
+// TRACE BUILDER
+// ================================================================================================
+
+/// Fills the fixed-layout execution trace (see the trace layout diagram in [crate](super)) across
+/// separate worker threads, one thread per disjoint column range, then stitches the column groups
+/// back into a single trace, returned as one vector per column.
+///
+/// The intended flow is: execute the program once to record the per-clock [Operation](super::Operation)
+/// log and the stack/aux state deltas, then call [TraceBuilder::build] with one closure per trace
+/// region (sys, stack, aux) that replays that log into its own [TraceFragment]. Because each
+/// closure only ever touches the columns in its own fragment, the regions can be filled
+/// concurrently with no cross-thread aliasing.
+///
+/// This builder is not yet called from a real trace-generation path: the prover that would own
+/// that call, and the `ProofOptions` that would carry a thread-count knob through to
+/// [TraceBuilder::new], both live in a crate this checkout doesn't contain (only
+/// `miden/benches/program_prover.rs` references `miden::ProofOptions`, as an external dependency
+/// it doesn't define). Wiring this in belongs in that crate once it exists; for now `build` is
+/// exercised only by the tests below.
+pub struct TraceBuilder {
+    num_rows: usize,
+    num_threads: usize,
+}
+
+impl TraceBuilder {
+    /// Returns a new [TraceBuilder] which will build a trace of `num_rows` rows, fanning the work
+    /// for each column range out across up to `num_threads` worker threads.
+    pub fn new(num_rows: usize, num_threads: usize) -> Self {
+        Self {
+            num_rows,
+            num_threads: num_threads.max(1),
+        }
+    }
+
+    /// Builds the full trace by running `fill_sys`, `fill_stack`, and `fill_aux`, and stitching
+    /// their outputs into a single `TRACE_WIDTH`-column trace, returned in column-major order (one
+    /// `Vec` of `num_rows` elements per column).
+    ///
+    /// Each closure receives a [TraceFragment] scoped to exactly the columns of its trace region
+    /// (`SYS_TRACE_RANGE`, `STACK_TRACE_RANGE`, or `AUX_TRACE_RANGE`) and is responsible for
+    /// filling every row of every column in that fragment.
+    ///
+    /// `self.num_threads()` caps how much of this work actually runs concurrently: with 3 or more
+    /// threads, every region gets its own worker thread; with 2, `fill_aux` runs on the same
+    /// thread as `fill_stack` once it finishes; with 1 (the default set by [TraceBuilder::new] for
+    /// `num_threads == 0`), all three regions are filled sequentially on the calling thread and no
+    /// thread is spawned at all.
+    pub fn build(
+        &self,
+        fill_sys: impl FnOnce(&mut TraceFragment) + Send,
+        fill_stack: impl FnOnce(&mut TraceFragment) + Send,
+        fill_aux: impl FnOnce(&mut TraceFragment) + Send,
+    ) -> Vec<Vec<Felt>> {
+        let mut columns: Vec<Vec<Felt>> = (0..TRACE_WIDTH)
+            .map(|_| vec![Felt::ZERO; self.num_rows])
+            .collect();
+
+        let (sys, rest) = columns.split_at_mut(SYS_TRACE_RANGE.end);
+        let (stack, aux) = rest.split_at_mut(STACK_TRACE_RANGE.end - SYS_TRACE_RANGE.end);
+        debug_assert_eq!(aux.len(), AUX_TRACE_RANGE.len());
+
+        match self.num_threads {
+            1 => {
+                let mut sys_fragment = TraceFragment::from_columns(SYS_TRACE_RANGE, sys);
+                fill_sys(&mut sys_fragment);
+                let mut stack_fragment = TraceFragment::from_columns(STACK_TRACE_RANGE, stack);
+                fill_stack(&mut stack_fragment);
+                let mut aux_fragment = TraceFragment::from_columns(AUX_TRACE_RANGE, aux);
+                fill_aux(&mut aux_fragment);
+            }
+            2 => {
+                std::thread::scope(|scope| {
+                    scope.spawn(|| {
+                        let mut fragment = TraceFragment::from_columns(SYS_TRACE_RANGE, sys);
+                        fill_sys(&mut fragment);
+                    });
+
+                    let mut stack_fragment = TraceFragment::from_columns(STACK_TRACE_RANGE, stack);
+                    fill_stack(&mut stack_fragment);
+                    let mut aux_fragment = TraceFragment::from_columns(AUX_TRACE_RANGE, aux);
+                    fill_aux(&mut aux_fragment);
+                });
+            }
+            _ => {
+                std::thread::scope(|scope| {
+                    scope.spawn(|| {
+                        let mut fragment = TraceFragment::from_columns(SYS_TRACE_RANGE, sys);
+                        fill_sys(&mut fragment);
+                    });
+                    scope.spawn(|| {
+                        let mut fragment = TraceFragment::from_columns(STACK_TRACE_RANGE, stack);
+                        fill_stack(&mut fragment);
+                    });
+                    scope.spawn(|| {
+                        let mut fragment = TraceFragment::from_columns(AUX_TRACE_RANGE, aux);
+                        fill_aux(&mut fragment);
+                    });
+                });
+            }
+        }
+
+        columns
+    }
+
+    /// Returns the number of worker threads this builder will use.
+    pub fn num_threads(&self) -> usize {
+        self.num_threads
+    }
+}
+
+impl<'a> TraceFragment<'a> {
+    fn from_columns(range: Range<usize>, columns: &'a mut [Vec<Felt>]) -> Self {
+        Self {
+            offset: range.start,
+            columns: columns.iter_mut().map(|col| col.as_mut_slice()).collect(),
+        }
+    }
+}
+
+// TESTS
+// ================================================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Fills every column of `fragment` with its own column index, offset by `region_tag`, so
+    /// that the assertions below can tell which closure actually touched which part of the trace.
+    fn fill_with_tag(fragment: &mut TraceFragment, region_tag: u64) {
+        for col_idx in 0..fragment.width() {
+            for row_idx in 0..fragment.len() {
+                fragment.set(col_idx, row_idx, Felt::new(region_tag + col_idx as u64));
+            }
+        }
+    }
+
+    fn build_and_check(num_threads: usize) {
+        let builder = TraceBuilder::new(2, num_threads);
+        let columns = builder.build(
+            |fragment| fill_with_tag(fragment, 100),
+            |fragment| fill_with_tag(fragment, 200),
+            |fragment| fill_with_tag(fragment, 300),
+        );
+
+        assert_eq!(columns.len(), TRACE_WIDTH);
+        for (col_idx, column) in columns[SYS_TRACE_RANGE].iter().enumerate() {
+            assert_eq!(column[0], Felt::new(100 + col_idx as u64));
+        }
+        for (col_idx, column) in columns[STACK_TRACE_RANGE].iter().enumerate() {
+            assert_eq!(column[0], Felt::new(200 + col_idx as u64));
+        }
+        for (col_idx, column) in columns[AUX_TRACE_RANGE].iter().enumerate() {
+            assert_eq!(column[0], Felt::new(300 + col_idx as u64));
+        }
+    }
+
+    #[test]
+    fn build_fills_every_region_with_one_thread() {
+        build_and_check(1);
+    }
+
+    #[test]
+    fn build_fills_every_region_with_two_threads() {
+        build_and_check(2);
+    }
+
+    #[test]
+    fn build_fills_every_region_with_three_threads() {
+        build_and_check(3);
+    }
+
+    #[test]
+    fn new_clamps_zero_threads_to_one() {
+        let builder = TraceBuilder::new(2, 0);
+        assert_eq!(builder.num_threads(), 1);
+    }
+}
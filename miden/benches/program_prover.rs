@@ -1,33 +1,224 @@
 use assembly::Assembler;
-use criterion::{criterion_group, criterion_main, Criterion};
-use miden::{ProofOptions};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use math::FieldElement;
+use miden::{HashFunction, ProofOptions};
+use processor::math::Felt;
 use std::time::Duration;
 use stdlib::StdLibrary;
 use vm_core::{Program, ProgramInputs};
-use log::{debug, error, info, logger, LevelFilter};
-use math::FieldElement;
-use processor::math::Felt;
 
+// CONFIGURATION
+// ================================================================================================
+
+/// Trace lengths (expressed as the number of Fibonacci terms computed) swept by the
+/// `program_prover` benchmark, from 2^10 up to 2^20.
+const TRACE_LENGTHS: [usize; 3] = [1 << 10, 1 << 16, 1 << 20];
+
+/// Blowup factors swept by the `proof_options` benchmark, holding query count, grinding factor,
+/// and hash function at their defaults below.
+const BLOWUP_FACTORS: [usize; 2] = [8, 16];
+
+/// Query counts swept by the `proof_options` benchmark, holding the other parameters fixed.
+const QUERY_COUNTS: [usize; 2] = [27, 54];
+
+/// Grinding factors swept by the `proof_options` benchmark, holding the other parameters fixed.
+const GRINDING_FACTORS: [u32; 2] = [16, 20];
+
+/// Hash functions swept by the `proof_options` benchmark, holding the other parameters fixed.
+const HASH_FUNCTIONS: [HashFunction; 2] = [HashFunction::Blake3_256, HashFunction::Rpo256];
+
+/// Default blowup, query count, and grinding factor used while a different single parameter is
+/// being swept.
+const DEFAULT_BLOWUP: usize = 8;
+const DEFAULT_NUM_QUERIES: usize = 27;
+const DEFAULT_GRINDING_FACTOR: u32 = 16;
+const DEFAULT_HASH_FUNCTION: HashFunction = HashFunction::Blake3_256;
+
+/// Number of independent Fibonacci instances packed into the 16 stack registers by the
+/// `wide_fibonacci` benchmark family (each instance only needs 2 registers: the running pair of
+/// terms).
+const NUM_WIDE_INSTANCES: usize = 8;
+
+// DEEP (SEQUENTIAL) FIBONACCI
+// ================================================================================================
+
+/// Sweeps the "deep" Fibonacci benchmark (a single, long sequential computation) across trace
+/// lengths, reporting both wall-clock time and proof size so that a regression in either prover
+/// performance or proof size shows up immediately.
+fn program_prover(c: &mut Criterion) {
+    let mut group = c.benchmark_group("program_prover");
+    group.measurement_time(Duration::from_secs(10));
+    group.sample_size(10);
+
+    for &n in TRACE_LENGTHS.iter() {
+        group.throughput(Throughput::Elements(1));
+        group.bench_with_input(BenchmarkId::from_parameter(n), &n, |bench, &n| {
+            let program = generate_fibonacci_program(n);
+            let inputs = load_fibonacci_inputs(&[0, 1]);
+            let options = ProofOptions::default();
+            println!(
+                "trace length = {n}: expected {n}-th Fibonacci term = {:?}",
+                compute_fibonacci(n)
+            );
+
+            // generate a proof once outside of the timed loop to report its size; criterion runs
+            // `bench.iter`'s closure many times per sample, and proof size doesn't vary between
+            // runs, so measuring and printing it there would only add noise to the timing
+            let (_outputs, proof) = miden::prove(&program, &inputs, &options).unwrap();
+            report_proof_size(n, proof.to_bytes().len());
+
+            bench.iter(|| {
+                miden::prove(&program, &inputs, &options).unwrap();
+            });
+        });
+    }
+
+    group.finish();
+}
+
+// WIDE (PARALLEL-INSTANCE) FIBONACCI
+// ================================================================================================
+
+/// Packs [NUM_WIDE_INSTANCES] mutually independent Fibonacci computations across the 16 stack
+/// registers of a single trace, rather than one long sequential computation occupying a single
+/// pair of registers. This lets prover cost *per logical computation* be compared directly against
+/// the deep sequential form above, since both sweeps can be driven by the same trace-length axis.
+fn wide_fibonacci(c: &mut Criterion) {
+    let mut group = c.benchmark_group("wide_fibonacci");
+    group.measurement_time(Duration::from_secs(10));
+    group.sample_size(10);
+
+    for &n in TRACE_LENGTHS.iter() {
+        group.throughput(Throughput::Elements(NUM_WIDE_INSTANCES as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(n), &n, |bench, &n| {
+            let program = generate_wide_fibonacci_program(n, NUM_WIDE_INSTANCES);
+            let stack_init = vec![0, 1].repeat(NUM_WIDE_INSTANCES);
+            let inputs = load_fibonacci_inputs(&stack_init);
+            let options = ProofOptions::default();
+
+            let (_outputs, proof) = miden::prove(&program, &inputs, &options).unwrap();
+            report_proof_size(n, proof.to_bytes().len());
+
+            bench.iter(|| {
+                miden::prove(&program, &inputs, &options).unwrap();
+            });
+        });
+    }
+
+    group.finish();
+}
+
+// PROOF OPTIONS SWEEP
+// ================================================================================================
+
+/// Sweeps `blowup`, query count, grinding factor, and hash function independently (one at a time,
+/// against the other three held at their defaults) across a fixed-size Fibonacci program, so that
+/// a regression introduced by a change to any single proving parameter is caught in isolation from
+/// the trace-length sweeps above.
+fn proof_options(c: &mut Criterion) {
+    let mut group = c.benchmark_group("proof_options");
+    group.measurement_time(Duration::from_secs(10));
+    group.sample_size(10);
 
-pub fn get_example(n: usize) -> Example<MemAdviceProvider> {
-    // generate the program and expected results
+    let n = TRACE_LENGTHS[1];
     let program = generate_fibonacci_program(n);
-    let expected_result = vec![compute_fibonacci(n).as_int()];
-    println!(
-        "Generated a program to compute {}-th Fibonacci term; expected result: {}",
-        n, expected_result[0]
-    );
+    let inputs = load_fibonacci_inputs(&[0, 1]);
+
+    for &blowup in BLOWUP_FACTORS.iter() {
+        let options = ProofOptions::new(
+            DEFAULT_NUM_QUERIES,
+            blowup,
+            DEFAULT_GRINDING_FACTOR,
+            DEFAULT_HASH_FUNCTION,
+        );
+        group.bench_with_input(BenchmarkId::new("blowup", blowup), &options, |bench, options| {
+            bench.iter(|| {
+                miden::prove(&program, &inputs, options).unwrap();
+            });
+        });
+    }
 
-    Example {
-        program,
-        stack_inputs: StackInputs::try_from_values([0, 1]).unwrap(),
-        advice_provider: MemAdviceProvider::empty(),
-        expected_result,
-        num_outputs: 1,
+    for &num_queries in QUERY_COUNTS.iter() {
+        let options = ProofOptions::new(
+            num_queries,
+            DEFAULT_BLOWUP,
+            DEFAULT_GRINDING_FACTOR,
+            DEFAULT_HASH_FUNCTION,
+        );
+        group.bench_with_input(
+            BenchmarkId::new("num_queries", num_queries),
+            &options,
+            |bench, options| {
+                bench.iter(|| {
+                    miden::prove(&program, &inputs, options).unwrap();
+                });
+            },
+        );
     }
+
+    for &grinding_factor in GRINDING_FACTORS.iter() {
+        let options = ProofOptions::new(
+            DEFAULT_NUM_QUERIES,
+            DEFAULT_BLOWUP,
+            grinding_factor,
+            DEFAULT_HASH_FUNCTION,
+        );
+        group.bench_with_input(
+            BenchmarkId::new("grinding_factor", grinding_factor),
+            &options,
+            |bench, options| {
+                bench.iter(|| {
+                    miden::prove(&program, &inputs, options).unwrap();
+                });
+            },
+        );
+    }
+
+    for hash_fn in HASH_FUNCTIONS.iter() {
+        let options = ProofOptions::new(
+            DEFAULT_NUM_QUERIES,
+            DEFAULT_BLOWUP,
+            DEFAULT_GRINDING_FACTOR,
+            *hash_fn,
+        );
+        group.bench_with_input(
+            BenchmarkId::new("hash_fn", format!("{hash_fn:?}")),
+            &options,
+            |bench, options| {
+                bench.iter(|| {
+                    miden::prove(&program, &inputs, options).unwrap();
+                });
+            },
+        );
+    }
+
+    group.finish();
 }
 
-/// Generates a program to compute the `n`-th term of Fibonacci sequence
+// HELPERS
+// ================================================================================================
+
+/// Builds [ProgramInputs] with the given initial stack values (and an empty advice tape/map/set
+/// list) by round-tripping them through the serde-based `InputFile` JSON format, rather than
+/// calling `ProgramInputs::from_stack_inputs` directly. This exercises the same loading path the
+/// CLI would use to read inputs authored as a file, in place of hard-coding them as Rust literals.
+///
+/// Requires `vm_core`'s `std` and `serde` features; this crate doesn't declare that dependency
+/// explicitly because this checkout has no `Cargo.toml` anywhere to declare it in.
+fn load_fibonacci_inputs(stack_init: &[u64]) -> ProgramInputs {
+    let stack_init_json = stack_init
+        .iter()
+        .map(u64::to_string)
+        .collect::<std::vec::Vec<_>>()
+        .join(", ");
+    let json = format!(
+        r#"{{"stack_init": [{stack_init_json}], "advice_tape": [], "advice_map": [], "advice_sets": []}}"#
+    );
+
+    ProgramInputs::from_reader(json.as_bytes()).unwrap()
+}
+
+/// Generates a program to compute the `n`-th term of Fibonacci sequence.
 fn generate_fibonacci_program(n: usize) -> Program {
     // the program is a simple repetition of 4 stack operations:
     // the first operation moves the 2nd stack item to the top,
@@ -44,10 +235,48 @@ fn generate_fibonacci_program(n: usize) -> Program {
         n - 1
     );
 
-    Assembler::default().compile(&program).unwrap()
+    Assembler::default()
+        .with_module_provider(StdLibrary::default())
+        .compile(&program)
+        .unwrap()
 }
 
-/// Computes the `n`-th term of Fibonacci sequence
+/// Generates a program which advances `num_instances` independent Fibonacci computations in
+/// lockstep, each confined to its own pair of stack registers, for `n - 1` steps.
+///
+/// Only the top 2 stack items are ever read or written by `swap dup.1 add` (see
+/// [generate_fibonacci_program]), so simply repeating that sequence `num_instances` times in a row
+/// would just keep advancing the same top pair and never touch the rest of the stack. Instead,
+/// each repetition advances the pair currently on top and then rotates it down past the other
+/// `num_instances - 1` pairs via a pair of `movdn`s, bringing the next instance's pair to the top
+/// in its place. After `num_instances` repetitions every instance has advanced by exactly one term
+/// and the original top-to-bottom ordering is restored, so the outer `repeat` can drive all
+/// instances through `n` terms together.
+fn generate_wide_fibonacci_program(n: usize, num_instances: usize) -> Program {
+    let window = 2 * num_instances;
+    let advance_and_rotate = format!(
+        "swap dup.1 add movdn.{depth} movdn.{depth}\n            ",
+        depth = window - 1
+    );
+    let row = advance_and_rotate.repeat(num_instances).trim_end().to_string();
+
+    let program = format!(
+        "begin
+            repeat.{}
+                {}
+            end
+        end",
+        n - 1,
+        row
+    );
+
+    Assembler::default()
+        .with_module_provider(StdLibrary::default())
+        .compile(&program)
+        .unwrap()
+}
+
+/// Computes the `n`-th term of Fibonacci sequence.
 fn compute_fibonacci(n: usize) -> Felt {
     let mut t0 = Felt::ZERO;
     let mut t1 = Felt::ONE;
@@ -59,41 +288,11 @@ fn compute_fibonacci(n: usize) -> Felt {
     t0
 }
 
-
-fn program_prover(c: &mut Criterion) {
-    // env_logger::Builder::new()
-    //         .format(|buf, record| writeln!(buf, "{}", record.args()))
-    //         .filter_level(log::LevelFilter::Debug)
-    //         .init();
-
-    let mut group = c.benchmark_group("program_prover");
-    group.measurement_time(Duration::from_secs(10));
-    group.sample_size(10);
-
-    group.bench_function("program_prover", |bench| {
-        bench.iter(|| {
-            let program = format!(
-                "begin
-                    repeat.{}
-                        swap dup.1 add
-                    end
-                end",
-                1 << 16
-            );
-
-            let program = Assembler::
-                .with_module_provider(StdLibrary::default())
-                .compile(&program)
-                .unwrap();
-
-            let inputs = ProgramInputs::from_stack_inputs(&[0, 1]).unwrap();
-
-            let (mut outputs, proof) = miden::prove(&program, &inputs, &ProofOptions::default()).unwrap();
-        });
-    });
-
-    group.finish();
+/// Prints the size of a proof generated for a trace of `n` rows, so that `cargo bench` output
+/// doubles as a record of proof-size regressions alongside the criterion-reported timings.
+fn report_proof_size(n: usize, size_in_bytes: usize) {
+    println!("trace length = {n}: proof size = {size_in_bytes} bytes");
 }
 
-criterion_group!(benches, program_prover);
+criterion_group!(benches, program_prover, wide_fibonacci, proof_options);
 criterion_main!(benches);